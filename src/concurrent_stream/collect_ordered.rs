@@ -0,0 +1,175 @@
+use std::boxed::Box;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use pin_project::pin_project;
+
+use super::Consumer;
+
+/// A future submitted to [`CollectOrdered`], tagged with the input index it
+/// was pulled from so results can be re-sorted once they're done.
+#[pin_project]
+struct IndexedFuture<F> {
+    index: usize,
+    #[pin]
+    future: F,
+}
+
+impl<F: Future> Future for IndexedFuture<F> {
+    type Output = (usize, F::Output);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let out = core::task::ready!(this.future.poll(cx));
+        Poll::Ready((*this.index, out))
+    }
+}
+
+/// A `Consumer` which collects results in the same order as the futures
+/// were submitted, even though they may complete out of order.
+///
+/// This `struct` is created by the [`collect_ordered`] method on the
+/// [`ConcurrentStreamExt`] trait. See its documentation for more.
+///
+/// [`collect_ordered`]: super::ConcurrentStreamExt::collect_ordered
+/// [`ConcurrentStreamExt`]: super::ConcurrentStreamExt
+pub struct CollectOrdered<F: Future> {
+    next_index: usize,
+    next_to_emit: usize,
+    in_flight: Vec<Pin<Box<IndexedFuture<F>>>>,
+    buffered: BTreeMap<usize, F::Output>,
+    output: Vec<F::Output>,
+}
+
+impl<F: Future> CollectOrdered<F> {
+    pub(crate) fn new() -> Self {
+        Self {
+            next_index: 0,
+            next_to_emit: 0,
+            in_flight: Vec::new(),
+            buffered: BTreeMap::new(),
+            output: Vec::new(),
+        }
+    }
+
+    /// Move the contiguous run of results starting at `next_to_emit` out of
+    /// the buffer and into the final, ordered output.
+    fn flush_ready_prefix(&mut self) {
+        while let Some(out) = self.buffered.remove(&self.next_to_emit) {
+            self.output.push(out);
+            self.next_to_emit += 1;
+        }
+    }
+}
+
+impl<Item, F> Consumer<Item, F> for CollectOrdered<F>
+where
+    F: Future<Output = Item>,
+{
+    type Output = Vec<Item>;
+
+    async fn send(&mut self, future: F) {
+        let index = self.next_index;
+        self.next_index += 1;
+        self.in_flight.push(Box::pin(IndexedFuture { index, future }));
+    }
+
+    async fn progress(&mut self) {
+        if self.in_flight.is_empty() {
+            return;
+        }
+
+        let mut any_ready = false;
+        core::future::poll_fn(|cx| {
+            let mut i = 0;
+            while i < self.in_flight.len() {
+                match self.in_flight[i].as_mut().poll(cx) {
+                    Poll::Ready((index, out)) => {
+                        self.buffered.insert(index, out);
+                        self.in_flight.swap_remove(i);
+                        any_ready = true;
+                    }
+                    Poll::Pending => i += 1,
+                }
+            }
+            if any_ready {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+
+        self.flush_ready_prefix();
+    }
+
+    async fn finish(mut self) -> Self::Output {
+        while !self.in_flight.is_empty() {
+            self.progress().await;
+        }
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::cell::Cell;
+
+    /// A future that resolves to `value` only after being polled
+    /// `polls_left` additional times, so a test can control completion order
+    /// independently of submission order.
+    struct ResolvesAfterPolls {
+        polls_left: Cell<usize>,
+        value: usize,
+    }
+
+    impl Future for ResolvesAfterPolls {
+        type Output = usize;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let left = self.polls_left.get();
+            if left == 0 {
+                Poll::Ready(self.value)
+            } else {
+                self.polls_left.set(left - 1);
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn preserves_submission_order_even_when_later_items_finish_first() {
+        futures_lite::future::block_on(async {
+            let mut consumer = CollectOrdered::new();
+
+            // `0` is submitted first but takes the longest to resolve; `2`
+            // is submitted last but resolves immediately. The final output
+            // must still be in submission order.
+            consumer
+                .send(ResolvesAfterPolls {
+                    polls_left: Cell::new(2),
+                    value: 0,
+                })
+                .await;
+            consumer
+                .send(ResolvesAfterPolls {
+                    polls_left: Cell::new(1),
+                    value: 1,
+                })
+                .await;
+            consumer
+                .send(ResolvesAfterPolls {
+                    polls_left: Cell::new(0),
+                    value: 2,
+                })
+                .await;
+
+            let output = consumer.finish().await;
+            assert_eq!(output, vec![0, 1, 2]);
+        });
+    }
+}
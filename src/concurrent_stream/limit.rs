@@ -0,0 +1,215 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll};
+use std::sync::Arc;
+
+use pin_project::pin_project;
+
+use super::collect_ordered::CollectOrdered;
+use super::{ConcurrentStream, Consumer};
+
+/// A concurrent stream that limits how many of its futures may be in flight
+/// at once.
+///
+/// This `struct` is created by the [`limit`] method on the
+/// [`ConcurrentStreamExt`] trait. See its documentation for more.
+///
+/// [`limit`]: ConcurrentStreamExt::limit
+#[derive(Debug)]
+pub struct Limit<CS: ConcurrentStream> {
+    inner: CS,
+    limit: usize,
+}
+
+impl<CS: ConcurrentStream> Limit<CS> {
+    pub(crate) fn new(inner: CS, limit: usize) -> Self {
+        assert!(limit > 0, "`limit` must be greater than zero");
+        Self { inner, limit }
+    }
+}
+
+impl<CS: ConcurrentStream> ConcurrentStream for Limit<CS> {
+    type Item = CS::Item;
+    type Future = Tracked<CS::Future>;
+
+    async fn drive<C>(self, consumer: C) -> C::Output
+    where
+        C: Consumer<Self::Item, Self::Future>,
+    {
+        let consumer = LimitConsumer {
+            inner: consumer,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            limit: self.limit,
+        };
+        self.inner.drive(consumer).await
+    }
+}
+
+/// A future submitted through a `LimitConsumer`, which decrements the shared
+/// in-flight counter once it resolves so a fresh slot opens up.
+#[pin_project]
+pub struct Tracked<F> {
+    #[pin]
+    future: F,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl<F: Future> Future for Tracked<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let out = core::task::ready!(this.future.poll(cx));
+        this.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Poll::Ready(out)
+    }
+}
+
+/// A `Consumer` which only forwards a new future to the inner consumer once
+/// fewer than `limit` futures are currently in flight; until then it only
+/// drives the inner consumer's progress, so that in-flight futures get a
+/// chance to complete and free up a slot.
+struct LimitConsumer<C> {
+    inner: C,
+    in_flight: Arc<AtomicUsize>,
+    limit: usize,
+}
+
+impl<Item, F, C> Consumer<Item, F> for LimitConsumer<C>
+where
+    F: Future<Output = Item>,
+    C: Consumer<Item, Tracked<F>>,
+{
+    type Output = C::Output;
+
+    async fn send(&mut self, future: F) {
+        while self.in_flight.load(Ordering::SeqCst) >= self.limit {
+            self.inner.progress().await;
+        }
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let future = Tracked {
+            future,
+            in_flight: self.in_flight.clone(),
+        };
+        self.inner.send(future).await;
+    }
+
+    async fn progress(&mut self) {
+        self.inner.progress().await;
+    }
+
+    async fn finish(self) -> Self::Output {
+        self.inner.finish().await
+    }
+}
+
+/// Extends [`ConcurrentStream`] with adapters that aren't part of its core,
+/// object-safe-sensitive definition.
+pub trait ConcurrentStreamExt: ConcurrentStream {
+    /// Limit how many of this stream's futures may be in flight at once.
+    ///
+    /// A fast source feeding slow per-item work can otherwise create an
+    /// unbounded number of outstanding futures; this caps that at `limit`,
+    /// the same way `buffer_unordered` does for regular streams.
+    fn limit(self, limit: usize) -> Limit<Self>
+    where
+        Self: Sized,
+    {
+        Limit::new(self, limit)
+    }
+
+    /// Concurrently drive this stream to completion, collecting the results
+    /// in the same order as the source stream, rather than completion order.
+    async fn collect_ordered(self) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.drive(CollectOrdered::new()).await
+    }
+}
+
+impl<CS: ConcurrentStream> ConcurrentStreamExt for CS {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::cell::Cell;
+
+    /// Tracks, across every `CountedPending` built from it, how many are
+    /// simultaneously in flight and the high-water mark reached.
+    struct Shared {
+        in_flight: Cell<usize>,
+        max_in_flight: Cell<usize>,
+    }
+
+    /// A future that only resolves after being polled `polls_left` extra
+    /// times, marking itself in flight on its first poll and out of flight
+    /// once it resolves, so a test can observe how many run concurrently.
+    struct CountedPending {
+        started: Cell<bool>,
+        polls_left: Cell<usize>,
+        shared: std::rc::Rc<Shared>,
+        output: usize,
+    }
+
+    impl Future for CountedPending {
+        type Output = usize;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            if !this.started.replace(true) {
+                let in_flight = this.shared.in_flight.get() + 1;
+                this.shared.in_flight.set(in_flight);
+                if in_flight > this.shared.max_in_flight.get() {
+                    this.shared.max_in_flight.set(in_flight);
+                }
+            }
+
+            let left = this.polls_left.get();
+            if left == 0 {
+                this.shared.in_flight.set(this.shared.in_flight.get() - 1);
+                Poll::Ready(this.output)
+            } else {
+                this.polls_left.set(left - 1);
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn never_exceeds_the_limit_and_admits_the_next_item_once_a_slot_frees() {
+        futures_lite::future::block_on(async {
+            let shared = std::rc::Rc::new(Shared {
+                in_flight: Cell::new(0),
+                max_in_flight: Cell::new(0),
+            });
+            let mut consumer = LimitConsumer {
+                inner: CollectOrdered::new(),
+                in_flight: Arc::new(AtomicUsize::new(0)),
+                limit: 2,
+            };
+
+            for i in 0..5 {
+                let future = CountedPending {
+                    started: Cell::new(false),
+                    // Stagger completion times so later items sometimes
+                    // finish before earlier ones.
+                    polls_left: Cell::new((i * 7) % 3),
+                    shared: shared.clone(),
+                    output: i,
+                };
+                consumer.send(future).await;
+            }
+
+            let results = consumer.finish().await;
+            assert_eq!(results, vec![0, 1, 2, 3, 4]);
+            assert!(
+                shared.max_in_flight.get() <= 2,
+                "never more than `limit` futures should be in flight at once, saw {}",
+                shared.max_in_flight.get()
+            );
+        });
+    }
+}
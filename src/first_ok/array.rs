@@ -19,17 +19,24 @@ where
     type Error = E;
 
     async fn first_ok(self) -> Result<Self::Output, Self::Error> {
+        assert!(N > 0, "`FirstOk` needs at least one future to race");
         FirstOk {
             elems: self.map(MaybeDone::new),
+            last_err: None,
         }
         .await
     }
 }
 
-/// Waits for two similarly-typed futures to complete.
+/// Races multiple similarly-typed fallible futures, resolving to the first
+/// one that completes successfully.
 ///
 /// Awaits multiple futures simultaneously, returning the output of the
-/// futures once both complete.
+/// first one to resolve with `Ok`, and dropping the rest. If every future
+/// resolves to `Err`, the last error encountered is returned.
+///
+/// Panics if raced over an empty array, since there is then no output and
+/// no error to produce.
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 #[pin_project]
 pub struct FirstOk<F, T, E, const N: usize>
@@ -38,6 +45,8 @@ where
     F: Future<Output = Result<T, E>>,
 {
     elems: [MaybeDone<F>; N],
+    /// Index of the most recently resolved `Err`, in time (not array) order.
+    last_err: Option<usize>,
 }
 
 impl<F, T, E, const N: usize> fmt::Debug for FirstOk<F, T, E, N>
@@ -47,7 +56,9 @@ where
     T: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Join").field("elems", &self.elems).finish()
+        f.debug_struct("FirstOk")
+            .field("elems", &self.elems)
+            .finish()
     }
 }
 
@@ -60,39 +71,46 @@ where
     type Output = Result<T, E>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        let mut all_done = true;
-
         let this = self.project();
 
-        for elem in this.elems.iter_mut() {
+        let mut all_done = true;
+
+        for (idx, elem) in this.elems.iter_mut().enumerate() {
             // SAFETY: we don't ever move the pinned container here; we only pin project
             let mut elem = unsafe { Pin::new_unchecked(elem) };
+
+            if elem.as_ref().output().is_some() {
+                // Already resolved in an earlier poll; nothing new to record.
+                continue;
+            }
+
             if let Poll::Pending = elem.as_mut().poll(cx) {
-                all_done = false
-            } else if let Some(Err(_)) = elem.as_ref().output() {
-                return Poll::Ready(Err(elem.take().unwrap().unwrap_err()));
+                all_done = false;
+                continue;
+            } else if let Some(Ok(_)) = elem.as_ref().output() {
+                // The first `Ok` wins the race; everything still in flight
+                // (including the rest of this loop) gets dropped once we
+                // return.
+                let out = elem.take().unwrap().unwrap();
+                return Poll::Ready(Ok(out));
+            } else {
+                // Just resolved to `Err`; this is the most recent failure
+                // we've seen so far, in time rather than array order.
+                *this.last_err = Some(idx);
             }
         }
 
         if all_done {
-            use core::mem::MaybeUninit;
-
-            // Create the result array based on the indices
-            let mut out: [MaybeUninit<T>; N] = {
-                // inlined version of unstable `MaybeUninit::uninit_array()`
-                // TODO: replace with `MaybeUninit::uninit_array()` when it becomes stable
-                unsafe { MaybeUninit::<[MaybeUninit<_>; N]>::uninit().assume_init() }
-            };
-
-            // NOTE: this clippy attribute can be removed once we can `collect` into `[usize; K]`.
-            #[allow(clippy::clippy::needless_range_loop)]
-            for (i, el) in this.elems.iter_mut().enumerate() {
-                // SAFETY: we don't ever move the pinned container here; we only pin project
-                let el = unsafe { Pin::new_unchecked(el) }.take().unwrap().unwrap();
-                out[i] = MaybeUninit::new(el);
-            }
-            let result = unsafe { out.as_ptr().cast::<[T; N]>().read() };
-            Poll::Ready(Ok(result))
+            // Every future resolved, and the loop above didn't find a single
+            // `Ok` among them, so they all failed. Report whichever one
+            // failed last, in time.
+            let idx = this
+                .last_err
+                .expect("FirstOk needs at least one future to race");
+            // SAFETY: we don't ever move the pinned container here; we only pin project
+            let mut elem = unsafe { Pin::new_unchecked(&mut this.elems[idx]) };
+            let err = elem.as_mut().take().unwrap().unwrap_err();
+            Poll::Ready(Err(err))
         } else {
             Poll::Pending
         }
@@ -106,23 +124,86 @@ mod test {
     use std::io::{self, Error, ErrorKind};
 
     #[test]
-    fn all_ok() {
+    fn all_ok_returns_first() {
         async_io::block_on(async {
             let res: io::Result<_> = [future::ready(Ok("hello")), future::ready(Ok("world"))]
                 .first_ok()
                 .await;
-            assert_eq!(res.unwrap(), ["hello", "world"]);
+            assert_eq!(res.unwrap(), "hello");
         })
     }
 
     #[test]
-    fn one_err() {
+    fn one_err_still_resolves_to_the_ok() {
         async_io::block_on(async {
             let err = Error::new(ErrorKind::Other, "oh no");
             let res: io::Result<_> = [future::ready(Ok("hello")), future::ready(Err(err))]
                 .first_ok()
                 .await;
-            assert_eq!(res.unwrap_err().to_string(), String::from("oh no"));
+            assert_eq!(res.unwrap(), "hello");
+        });
+    }
+
+    #[test]
+    fn all_err_returns_last_error() {
+        async_io::block_on(async {
+            let a = Error::new(ErrorKind::Other, "first");
+            let b = Error::new(ErrorKind::Other, "second");
+            let res: io::Result<&str> = [future::ready(Err(a)), future::ready(Err(b))]
+                .first_ok()
+                .await;
+            assert_eq!(res.unwrap_err().to_string(), String::from("second"));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "needs at least one future to race")]
+    fn empty_array_panics() {
+        async_io::block_on(async {
+            let empty: [std::future::Ready<io::Result<&str>>; 0] = [];
+            let _ = empty.first_ok().await;
+        });
+    }
+
+    /// A future that resolves to `Err(msg)` only after being polled
+    /// `polls_left` additional times, re-scheduling itself via the waker in
+    /// the meantime.
+    struct ErrAfterPolls {
+        polls_left: std::cell::Cell<usize>,
+        msg: &'static str,
+    }
+
+    impl Future for ErrAfterPolls {
+        type Output = io::Result<&'static str>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let left = self.polls_left.get();
+            if left == 0 {
+                Poll::Ready(Err(Error::new(ErrorKind::Other, self.msg)))
+            } else {
+                self.polls_left.set(left - 1);
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn all_err_returns_last_error_by_completion_time_not_array_index() {
+        async_io::block_on(async {
+            // `a` sits first in the array but takes longer to resolve than
+            // `b`; its error must still win, since it's the one that failed
+            // most recently.
+            let a = ErrAfterPolls {
+                polls_left: std::cell::Cell::new(2),
+                msg: "a failed last",
+            };
+            let b = ErrAfterPolls {
+                polls_left: std::cell::Cell::new(0),
+                msg: "b failed first",
+            };
+            let res: io::Result<&str> = [a, b].first_ok().await;
+            assert_eq!(res.unwrap_err().to_string(), "a failed last");
         });
     }
 }
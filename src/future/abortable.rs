@@ -0,0 +1,308 @@
+//! A future that can be remotely cancelled using an `AbortHandle`.
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::UnsafeCell;
+use core::fmt;
+use core::future::Future;
+use core::hint;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use pin_project::pin_project;
+
+/// A future that has been made abortable.
+///
+/// This `struct` is created by the [`abortable`] method on the
+/// [`FutureExt`] trait. See its documentation for more.
+///
+/// [`abortable`]: super::ext::FutureExt::abortable
+/// [`FutureExt`]: super::ext::FutureExt
+#[pin_project]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Abortable<F> {
+    #[pin]
+    future: F,
+    inner: Arc<AbortInner>,
+    /// This `Abortable`'s own slot in `inner.wakers`, so several `Abortable`s
+    /// sharing one registration can each register their own waker without
+    /// clobbering one another's.
+    waker_slot: usize,
+}
+
+impl<F> fmt::Debug for Abortable<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Abortable")
+            .field("aborted", &self.inner.aborted.load(Ordering::Acquire))
+            .finish()
+    }
+}
+
+impl<F> Abortable<F>
+where
+    F: Future,
+{
+    /// Wrap `future` so it can be aborted through the paired `AbortHandle`
+    /// behind `reg`.
+    pub fn new(future: F, reg: AbortRegistration) -> Self {
+        let waker_slot = {
+            let mut wakers = reg.inner.wakers.lock();
+            wakers.push(None);
+            wakers.len() - 1
+        };
+        Self {
+            future,
+            inner: reg.inner,
+            waker_slot,
+        }
+    }
+}
+
+impl<F> Future for Abortable<F>
+where
+    F: Future,
+{
+    type Output = Result<F::Output, Aborted>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if this.inner.aborted.load(Ordering::Acquire) {
+            return Poll::Ready(Err(Aborted));
+        }
+
+        this.inner.wakers.lock()[*this.waker_slot] = Some(cx.waker().clone());
+
+        // Check again in case `abort` raced with us registering the waker
+        // above.
+        if this.inner.aborted.load(Ordering::Acquire) {
+            return Poll::Ready(Err(Aborted));
+        }
+
+        this.future.poll(cx).map(Ok)
+    }
+}
+
+/// A handle to remotely abort an `Abortable` future once it has been run.
+///
+/// `AbortHandle::abort` may be called even after the future it refers to has
+/// already completed or been dropped; in that case the call is a no-op.
+#[derive(Debug, Clone)]
+pub struct AbortHandle {
+    inner: Arc<AbortInner>,
+}
+
+impl AbortHandle {
+    /// Creates an `(AbortHandle, AbortRegistration)` pair which can be used
+    /// to abort one or more `Abortable` futures.
+    pub fn new_pair() -> (Self, AbortRegistration) {
+        let inner = Arc::new(AbortInner {
+            wakers: WakerSlots::new(),
+            aborted: AtomicBool::new(false),
+        });
+
+        (
+            Self {
+                inner: inner.clone(),
+            },
+            AbortRegistration { inner },
+        )
+    }
+
+    /// Abort the `Abortable` future(s) associated with this handle.
+    ///
+    /// Awaking the task promptly is best-effort; any future polled after
+    /// this call observes the abort on its very next poll.
+    pub fn abort(&self) {
+        self.inner.aborted.store(true, Ordering::Release);
+        // Take the wakers but keep every slot, so an `Abortable` that is
+        // registering its waker concurrently still finds its own index.
+        for waker in self.inner.wakers.lock().iter_mut().filter_map(Option::take) {
+            waker.wake();
+        }
+    }
+
+    /// Checks whether `AbortHandle::abort` has been called.
+    pub fn is_aborted(&self) -> bool {
+        self.inner.aborted.load(Ordering::Acquire)
+    }
+}
+
+/// A registration handle for an `AbortHandle` that's used to create an
+/// `Abortable` future.
+///
+/// This is used to register with an `Abortable` future; the same
+/// registration can be shared between several `Abortable`s, so that one
+/// handle aborts all of them at once.
+#[derive(Debug, Clone)]
+pub struct AbortRegistration {
+    inner: Arc<AbortInner>,
+}
+
+struct AbortInner {
+    /// One slot per `Abortable` built from this registration, so `abort`
+    /// can wake every one of them even when they're driven by different
+    /// tasks.
+    wakers: WakerSlots,
+    aborted: AtomicBool,
+}
+
+impl fmt::Debug for AbortInner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AbortInner")
+            .field("aborted", &self.aborted.load(Ordering::Acquire))
+            .finish()
+    }
+}
+
+/// A spinlock-guarded list of waker slots, used instead of `std::sync::Mutex`
+/// so this module stays usable without `std`.
+struct WakerSlots {
+    locked: AtomicBool,
+    slots: UnsafeCell<Vec<Option<Waker>>>,
+}
+
+// SAFETY: access to `slots` is only ever made while `locked` is held.
+unsafe impl Send for WakerSlots {}
+unsafe impl Sync for WakerSlots {}
+
+impl WakerSlots {
+    fn new() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            slots: UnsafeCell::new(Vec::new()),
+        }
+    }
+
+    fn lock(&self) -> WakerSlotsGuard<'_> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        WakerSlotsGuard { slots: self }
+    }
+}
+
+struct WakerSlotsGuard<'a> {
+    slots: &'a WakerSlots,
+}
+
+impl Deref for WakerSlotsGuard<'_> {
+    type Target = Vec<Option<Waker>>;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: holding the guard means we hold the lock.
+        unsafe { &*self.slots.slots.get() }
+    }
+}
+
+impl DerefMut for WakerSlotsGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: holding the guard means we hold the lock.
+        unsafe { &mut *self.slots.slots.get() }
+    }
+}
+
+impl Drop for WakerSlotsGuard<'_> {
+    fn drop(&mut self) {
+        self.slots.locked.store(false, Ordering::Release);
+    }
+}
+
+/// Indicator that the `Abortable` future was aborted.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Aborted;
+
+impl fmt::Display for Aborted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`Abortable` future has been aborted")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Aborted {}
+
+#[cfg(test)]
+mod test {
+    use super::super::ext::FutureExt;
+    use super::*;
+    use std::future;
+
+    #[test]
+    fn runs_to_completion_when_not_aborted() {
+        futures_lite::future::block_on(async {
+            let (fut, _handle) = future::ready(42).abortable();
+            assert_eq!(fut.await, Ok(42));
+        });
+    }
+
+    #[test]
+    fn aborted_before_poll_never_resolves_the_inner_future() {
+        futures_lite::future::block_on(async {
+            let (fut, handle) = future::pending::<()>().abortable();
+            handle.abort();
+            assert_eq!(fut.await, Err(Aborted));
+        });
+    }
+
+    #[test]
+    fn abort_wakes_every_abortable_sharing_a_registration() {
+        use core::task::{RawWaker, RawWakerVTable};
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        fn tracking_waker(woken: Rc<Cell<bool>>) -> Waker {
+            fn clone(ptr: *const ()) -> RawWaker {
+                let rc = unsafe { Rc::from_raw(ptr as *const Cell<bool>) };
+                let cloned = rc.clone();
+                core::mem::forget(rc);
+                RawWaker::new(Rc::into_raw(cloned) as *const (), &VTABLE)
+            }
+            fn wake(ptr: *const ()) {
+                wake_by_ref(ptr);
+                drop_(ptr);
+            }
+            fn wake_by_ref(ptr: *const ()) {
+                let rc = unsafe { Rc::from_raw(ptr as *const Cell<bool>) };
+                rc.set(true);
+                core::mem::forget(rc);
+            }
+            fn drop_(ptr: *const ()) {
+                unsafe { drop(Rc::from_raw(ptr as *const Cell<bool>)) };
+            }
+            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop_);
+            let ptr = Rc::into_raw(woken) as *const ();
+            unsafe { Waker::from_raw(RawWaker::new(ptr, &VTABLE)) }
+        }
+
+        // Two `Abortable`s sharing one registration, each "driven" by a
+        // different waker (as if polled by two separate tasks).
+        let (handle, reg) = AbortHandle::new_pair();
+        let mut a = std::pin::pin!(Abortable::new(future::pending::<()>(), reg.clone()));
+        let mut b = std::pin::pin!(Abortable::new(future::pending::<()>(), reg));
+
+        let a_woken = Rc::new(Cell::new(false));
+        let b_woken = Rc::new(Cell::new(false));
+        let a_waker = tracking_waker(a_woken.clone());
+        let b_waker = tracking_waker(b_woken.clone());
+
+        assert_eq!(
+            a.as_mut().poll(&mut Context::from_waker(&a_waker)),
+            Poll::Pending
+        );
+        assert_eq!(
+            b.as_mut().poll(&mut Context::from_waker(&b_waker)),
+            Poll::Pending
+        );
+
+        handle.abort();
+
+        assert!(a_woken.get(), "`a`'s waker should be woken on abort");
+        assert!(b_woken.get(), "`b`'s waker should be woken on abort");
+    }
+}
@@ -0,0 +1,95 @@
+//! A future that catches panics raised while polling its inner future.
+
+#![cfg(feature = "std")]
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::any::Any;
+use std::boxed::Box;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use pin_project::pin_project;
+
+/// A future that catches panics raised while polling its inner future.
+///
+/// This `struct` is created by the [`catch_unwind`] method on the
+/// [`FutureExt`] trait. See its documentation for more.
+///
+/// Once a panic has been caught, the inner future is never polled again;
+/// polling `CatchUnwind` after that point only panics.
+///
+/// [`catch_unwind`]: super::ext::FutureExt::catch_unwind
+/// [`FutureExt`]: super::ext::FutureExt
+#[pin_project]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CatchUnwind<F> {
+    #[pin]
+    future: F,
+    done: bool,
+}
+
+impl<F> CatchUnwind<F> {
+    pub(crate) fn new(future: F) -> Self {
+        Self {
+            future,
+            done: false,
+        }
+    }
+}
+
+impl<F> fmt::Debug for CatchUnwind<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CatchUnwind").field("done", &self.done).finish()
+    }
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        assert!(
+            !*this.done,
+            "`CatchUnwind` must not be polled again after it has completed"
+        );
+
+        match catch_unwind(AssertUnwindSafe(|| this.future.poll(cx))) {
+            Ok(Poll::Ready(out)) => {
+                *this.done = true;
+                Poll::Ready(Ok(out))
+            }
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(panic) => {
+                *this.done = true;
+                Poll::Ready(Err(panic))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::ext::FutureExt;
+    use std::future;
+
+    #[test]
+    fn resolves_normally_when_no_panic_occurs() {
+        futures_lite::future::block_on(async {
+            let out = future::ready(42).catch_unwind().await;
+            assert_eq!(out.unwrap(), 42);
+        });
+    }
+
+    #[test]
+    fn catches_a_panic_raised_while_polling() {
+        futures_lite::future::block_on(async {
+            let fut = future::poll_fn(|_| -> core::task::Poll<()> { panic!("oh no") });
+            let out = fut.catch_unwind().await;
+            assert!(out.is_err());
+        });
+    }
+}
@@ -0,0 +1,34 @@
+use core::future::Future;
+
+use super::abortable::{AbortHandle, Abortable};
+
+#[cfg(feature = "std")]
+use super::catch_unwind::CatchUnwind;
+
+/// Extends [`Future`] with adapters that aren't part of its core definition.
+pub trait FutureExt: Future {
+    /// Creates a new `Abortable` future along with an `AbortHandle` which
+    /// can be used to stop it from the outside.
+    fn abortable(self) -> (Abortable<Self>, AbortHandle)
+    where
+        Self: Sized,
+    {
+        let (handle, reg) = AbortHandle::new_pair();
+        (Abortable::new(self, reg), handle)
+    }
+
+    /// Catches panics raised while polling this future, turning them into a
+    /// `Result` instead of unwinding through the poller.
+    ///
+    /// Once a panic has been caught, the returned future must not be polled
+    /// again.
+    #[cfg(feature = "std")]
+    fn catch_unwind(self) -> CatchUnwind<Self>
+    where
+        Self: Sized,
+    {
+        CatchUnwind::new(self)
+    }
+}
+
+impl<F: Future> FutureExt for F {}
@@ -1,5 +1,5 @@
 use super::Join as JoinTrait;
-use crate::utils::{self, PollState};
+use crate::utils::{self, construct_tuple, PollState, WakerArray};
 
 use core::fmt::{self, Debug};
 use core::future::{Future, IntoFuture};
@@ -9,82 +9,79 @@ use core::task::{Context, Poll};
 
 use pin_project::pin_project;
 
-macro_rules! construct_tuple {
-    (@inner $value:tt, $ignore:ident) => {
-        $value
-    };
-    ($value:path, $($F:ident,)*) => {
-        ($(construct_tuple!(@inner $value, $F),)*)
-    };
-}
-
-macro_rules! maybe_poll {
-    ($idx:tt, $len:ident, $this:ident, $futures:ident, $cx:ident) => {
+macro_rules! poll_leaf {
+    ($idx:tt, $this:ident, $futures:ident) => {
         if $this.states.$idx.is_pending() {
-            if let Poll::Ready(out) = $futures.$idx.poll($cx) {
-                $this.outputs.$idx = MaybeUninit::new(out);
-                $this.states.$idx.set_consumed();
-                *$this.len -= 1;
+            // Only poll this leaf if its waker fired (or this is the very
+            // first poll, in which case every bit starts out set).
+            let ready = $this.wakers.readiness().clear_ready($idx);
+            if ready {
+                let mut cx = Context::from_waker($this.wakers.get($idx));
+                if let Poll::Ready(out) = $futures.$idx.poll(&mut cx) {
+                    $this.outputs.$idx = MaybeUninit::new(out);
+                    $this.states.$idx.set_consumed();
+                    *$this.len -= 1;
+                }
             }
         }
     };
 }
 
-macro_rules! poll_all_pending {
-    (@inner 0, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(0, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 1, $len, $this, $futures, $cx, ($($rest,)*));
+macro_rules! poll_ready_leaves {
+    (@inner 0, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(0, $this, $futures);
+        poll_ready_leaves!(@inner 1, $this, $futures, ($($rest,)*));
     };
-    (@inner 1, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(1, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 2, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 1, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(1, $this, $futures);
+        poll_ready_leaves!(@inner 2, $this, $futures, ($($rest,)*));
     };
-    (@inner 2, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(2, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 3, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 2, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(2, $this, $futures);
+        poll_ready_leaves!(@inner 3, $this, $futures, ($($rest,)*));
     };
-    (@inner 3, $len:ident, $this:ident, $futures:ident, $cx:ident, ($fut:ident, $($rest:ident,)*)) => {
-        maybe_poll!(3, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 4, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 3, $this:ident, $futures:ident, ($fut:ident, $($rest:ident,)*)) => {
+        poll_leaf!(3, $this, $futures);
+        poll_ready_leaves!(@inner 4, $this, $futures, ($($rest,)*));
     };
-    (@inner 4, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(4, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 5, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 4, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(4, $this, $futures);
+        poll_ready_leaves!(@inner 5, $this, $futures, ($($rest,)*));
     };
-    (@inner 5, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(5, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 6, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 5, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(5, $this, $futures);
+        poll_ready_leaves!(@inner 6, $this, $futures, ($($rest,)*));
     };
-    (@inner 6, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(6, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 7, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 6, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(6, $this, $futures);
+        poll_ready_leaves!(@inner 7, $this, $futures, ($($rest,)*));
     };
-    (@inner 7, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(7, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 8, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 7, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(7, $this, $futures);
+        poll_ready_leaves!(@inner 8, $this, $futures, ($($rest,)*));
     };
-    (@inner 8, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(8, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 9, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 8, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(8, $this, $futures);
+        poll_ready_leaves!(@inner 9, $this, $futures, ($($rest,)*));
     };
-    (@inner 9, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(9, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 10, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 9, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(9, $this, $futures);
+        poll_ready_leaves!(@inner 10, $this, $futures, ($($rest,)*));
     };
-    (@inner 10, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(10, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 11, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 10, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(10, $this, $futures);
+        poll_ready_leaves!(@inner 11, $this, $futures, ($($rest,)*));
     };
-    (@inner 11, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(11, $len, $this, $futures, $cx);
-        poll_all_pending!(@inner 12, $len, $this, $futures, $cx, ($($rest,)*));
+    (@inner 11, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(11, $this, $futures);
+        poll_ready_leaves!(@inner 12, $this, $futures, ($($rest,)*));
     };
-    (@inner 12, $len:ident, $this:ident, $futures:ident, $cx:ident, ($popped:ident, $($rest:ident,)*)) => {
-        maybe_poll!(12, $len, $this, $cx);
+    (@inner 12, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*)) => {
+        poll_leaf!(12, $this, $futures);
     };
-    (@inner $ignore:literal, $len:ident, $this:ident, $futures:ident, $cx:ident, ()) => { };
-    ($len:ident, $this:ident, $futures:ident, $cx:ident, $($F:ident,)*) => {
-        poll_all_pending!(@inner 0, $len, $this, $futures, $cx, ($($F,)*));
+    (@inner $ignore:literal, $this:ident, $futures:ident, ()) => { };
+    ($this:ident, $futures:ident, $($F:ident,)*) => {
+        poll_ready_leaves!(@inner 0, $this, $futures, ($($F,)*));
     };
 }
 
@@ -113,6 +110,7 @@ macro_rules! impl_join_tuple {
             #[pin] futures: $mod_name::Futures<$($F,)*>,
             outputs: ($(MaybeUninit<$F::Output>,)*),
             states: construct_tuple!(PollState, $($F,)*),
+            wakers: WakerArray<{ utils::tuple_len!($($F,)*) as usize }>,
         }
 
         impl<$($F),*> Debug for $StructName<$($F),*>
@@ -139,7 +137,18 @@ macro_rules! impl_join_tuple {
                 let mut this = self.project();
                 let futures = this.futures.project();
 
-                poll_all_pending!(LEN, this, futures, cx, $($F,)*);
+                // Only bother with the waker bookkeeping while there's still
+                // something to wait on; on the very first poll every bit is
+                // set, so everything gets polled once.
+                if *this.len > 0 {
+                    let mut readiness = this.wakers.readiness();
+                    readiness.set_waker(cx.waker());
+                    let any_ready = readiness.any_ready();
+                    drop(readiness);
+                    if any_ready {
+                        poll_ready_leaves!(this, futures, $($F,)*);
+                    }
+                }
 
                 if *this.len <= 0 {
                     let out = unsafe {(this.outputs as *const _ as *const ($($F::Output,)*)).read()};
@@ -165,7 +174,8 @@ macro_rules! impl_join_tuple {
                     len: LEN,
                     futures: $mod_name::Futures($($F.into_future(),)* ()),
                     outputs: ($(MaybeUninit::<$F::Output>::uninit(),)*),
-                    states: construct_tuple!(PollState::Pending, $($F,)*)
+                    states: construct_tuple!(PollState::Pending, $($F,)*),
+                    wakers: WakerArray::new(),
                 }
             }
         }
@@ -224,4 +234,83 @@ mod test {
             assert_eq!((a, b, c).join().await, ("hello", "world", 12));
         });
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn join_only_repolls_the_leaf_whose_waker_fired() {
+        use core::cell::Cell;
+        use core::task::{RawWaker, RawWakerVTable, Waker};
+        use std::rc::Rc;
+
+        fn noop_waker() -> Waker {
+            const VTABLE: RawWakerVTable = RawWakerVTable::new(
+                |_| RawWaker::new(core::ptr::null(), &VTABLE),
+                |_| {},
+                |_| {},
+                |_| {},
+            );
+            unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) }
+        }
+
+        #[derive(Clone, Default)]
+        struct Shared {
+            polls: Rc<Cell<usize>>,
+            waker: Rc<Cell<Option<Waker>>>,
+        }
+
+        struct PendingThenReady {
+            shared: Shared,
+            ready_after: usize,
+            output: i32,
+        }
+
+        impl Future for PendingThenReady {
+            type Output = i32;
+
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<i32> {
+                let this = self.get_mut();
+                let polls = this.shared.polls.get() + 1;
+                this.shared.polls.set(polls);
+                if polls > this.ready_after {
+                    Poll::Ready(this.output)
+                } else {
+                    this.shared.waker.set(Some(cx.waker().clone()));
+                    Poll::Pending
+                }
+            }
+        }
+
+        // `a` resolves the second time it's polled; `b` never resolves, so
+        // any spurious re-poll of it would show up as an extra poll count.
+        let a_shared = Shared::default();
+        let b_shared = Shared::default();
+        let a = PendingThenReady {
+            shared: a_shared.clone(),
+            ready_after: 1,
+            output: 1,
+        };
+        let b = PendingThenReady {
+            shared: b_shared.clone(),
+            ready_after: usize::MAX,
+            output: 2,
+        };
+
+        let fut = (a, b).join();
+        let mut fut = std::pin::pin!(fut);
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        // First poll: every leaf starts "ready", so both get polled once.
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(a_shared.polls.get(), 1);
+        assert_eq!(b_shared.polls.get(), 1);
+
+        // Wake only `a`'s leaf waker.
+        a_shared.waker.take().unwrap().wake();
+
+        // Second poll must only re-poll `a`, not `b`.
+        assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(a_shared.polls.get(), 2);
+        assert_eq!(b_shared.polls.get(), 1);
+    }
+}
@@ -0,0 +1,23 @@
+mod tuple;
+
+use core::future::Future;
+
+/// Wait for all futures to complete successfully, or abort early on the
+/// first error.
+///
+/// Unlike [`Join`], which always waits for every future to complete,
+/// `TryJoin` stops polling and resolves as soon as any future resolves to
+/// an `Err`, dropping the futures that are still in flight.
+///
+/// [`Join`]: crate::future::Join
+pub trait TryJoin {
+    /// The resulting output type.
+    type Output;
+
+    /// Which kind of future are we turning this into?
+    type Future: Future<Output = Self::Output>;
+
+    /// Waits for multiple futures to complete, either returning once all of
+    /// them resolve successfully, or as soon as any one of them fails.
+    fn try_join(self) -> Self::Future;
+}
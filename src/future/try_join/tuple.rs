@@ -0,0 +1,369 @@
+use super::TryJoin as TryJoinTrait;
+use crate::utils::{self, construct_tuple, PollState, WakerArray};
+
+use core::convert::Infallible;
+use core::fmt::{self, Debug};
+use core::future::{Future, IntoFuture};
+use core::mem::MaybeUninit;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::future::TryFuture;
+use pin_project::pin_project;
+
+macro_rules! drop_consumed {
+    ($idx:tt, $this:ident) => {
+        if !$this.states.$idx.is_pending() {
+            // SAFETY: every slot whose state isn't `Pending` anymore holds an
+            // initialized `Ok` value that nothing else will ever read, since
+            // we're about to abandon this future entirely.
+            unsafe { $this.outputs.$idx.assume_init_drop() };
+        }
+    };
+}
+
+macro_rules! try_poll_leaf {
+    ($idx:tt, $this:ident, $futures:ident, $($all:ident,)*) => {
+        if $this.states.$idx.is_pending() {
+            let ready = $this.wakers.readiness().clear_ready($idx);
+            if ready {
+                let mut cx = Context::from_waker($this.wakers.get($idx));
+                match $futures.$idx.try_poll(&mut cx) {
+                    Poll::Ready(Ok(out)) => {
+                        $this.outputs.$idx = MaybeUninit::new(out);
+                        $this.states.$idx.set_consumed();
+                        *$this.len -= 1;
+                    }
+                    Poll::Ready(Err(err)) => {
+                        // SAFETY: drop exactly the slots that were already
+                        // filled in by an earlier, successful poll; the slot
+                        // that just errored was never written to, and we're
+                        // about to return without touching the rest.
+                        drop_consumed_leaves!($this, $($all,)*);
+                        return Poll::Ready(Err(err));
+                    }
+                    Poll::Pending => {}
+                }
+            }
+        }
+    };
+}
+
+macro_rules! drop_consumed_leaves {
+    (@inner 0, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(0, $this);
+        drop_consumed_leaves!(@inner 1, $this, ($($rest,)*));
+    };
+    (@inner 1, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(1, $this);
+        drop_consumed_leaves!(@inner 2, $this, ($($rest,)*));
+    };
+    (@inner 2, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(2, $this);
+        drop_consumed_leaves!(@inner 3, $this, ($($rest,)*));
+    };
+    (@inner 3, $this:ident, ($fut:ident, $($rest:ident,)*)) => {
+        drop_consumed!(3, $this);
+        drop_consumed_leaves!(@inner 4, $this, ($($rest,)*));
+    };
+    (@inner 4, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(4, $this);
+        drop_consumed_leaves!(@inner 5, $this, ($($rest,)*));
+    };
+    (@inner 5, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(5, $this);
+        drop_consumed_leaves!(@inner 6, $this, ($($rest,)*));
+    };
+    (@inner 6, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(6, $this);
+        drop_consumed_leaves!(@inner 7, $this, ($($rest,)*));
+    };
+    (@inner 7, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(7, $this);
+        drop_consumed_leaves!(@inner 8, $this, ($($rest,)*));
+    };
+    (@inner 8, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(8, $this);
+        drop_consumed_leaves!(@inner 9, $this, ($($rest,)*));
+    };
+    (@inner 9, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(9, $this);
+        drop_consumed_leaves!(@inner 10, $this, ($($rest,)*));
+    };
+    (@inner 10, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(10, $this);
+        drop_consumed_leaves!(@inner 11, $this, ($($rest,)*));
+    };
+    (@inner 11, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(11, $this);
+        drop_consumed_leaves!(@inner 12, $this, ($($rest,)*));
+    };
+    (@inner 12, $this:ident, ($popped:ident, $($rest:ident,)*)) => {
+        drop_consumed!(12, $this);
+    };
+    (@inner $ignore:literal, $this:ident, ()) => { };
+    ($this:ident, $($F:ident,)*) => {
+        drop_consumed_leaves!(@inner 0, $this, ($($F,)*));
+    };
+}
+
+macro_rules! try_poll_ready_leaves {
+    (@inner 0, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(0, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 1, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 1, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(1, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 2, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 2, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(2, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 3, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 3, $this:ident, $futures:ident, ($fut:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(3, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 4, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 4, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(4, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 5, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 5, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(5, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 6, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 6, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(6, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 7, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 7, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(7, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 8, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 8, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(8, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 9, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 9, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(9, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 10, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 10, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(10, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 11, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 11, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(11, $this, $futures, $($all,)*);
+        try_poll_ready_leaves!(@inner 12, $this, $futures, ($($rest,)*), ($($all,)*));
+    };
+    (@inner 12, $this:ident, $futures:ident, ($popped:ident, $($rest:ident,)*), ($($all:ident,)*)) => {
+        try_poll_leaf!(12, $this, $futures, $($all,)*);
+    };
+    (@inner $ignore:literal, $this:ident, $futures:ident, (), ($($all:ident,)*)) => { };
+    ($this:ident, $futures:ident, $($F:ident,)*) => {
+        try_poll_ready_leaves!(@inner 0, $this, $futures, ($($F,)*), ($($F,)*));
+    };
+}
+
+macro_rules! impl_try_join_tuple {
+    ($mod_name:ident $StructName:ident $first:ident $($F:ident)*) => {
+        mod $mod_name {
+            use super::*;
+
+            #[pin_project]
+            pub(super) struct Futures<$first: Future, $($F: Future,)*>(#[pin] pub(super) $first, $(#[pin] pub(super) $F,)* pub(super) ());
+        }
+
+        /// Waits for multiple fallible futures to complete, short-circuiting on
+        /// the first error.
+        ///
+        /// This `struct` is created by the [`try_join`] method on the
+        /// [`TryJoin`] trait. See its documentation for more.
+        ///
+        /// [`try_join`]: crate::future::TryJoin::try_join
+        /// [`TryJoin`]: crate::future::TryJoin
+        #[pin_project]
+        #[must_use = "futures do nothing unless you `.await` or poll them"]
+        #[allow(non_snake_case)]
+        pub struct $StructName<$first, $($F,)*>
+        where
+            $first: TryFuture,
+            $($F: TryFuture<Error = $first::Error>,)*
+        {
+            len: u32,
+            #[pin] futures: $mod_name::Futures<$first, $($F,)*>,
+            outputs: (MaybeUninit<$first::Ok>, $(MaybeUninit<$F::Ok>,)*),
+            states: construct_tuple!(PollState, $first, $($F,)*),
+            wakers: WakerArray<{ utils::tuple_len!($first, $($F,)*) as usize }>,
+        }
+
+        impl<$first, $($F,)*> Debug for $StructName<$first, $($F,)*>
+        where
+            $first: TryFuture + Debug,
+            $($F: TryFuture<Error = $first::Error> + Debug,)*
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple("TryJoin")
+                    .field(&self.states)
+                    .finish()
+            }
+        }
+
+        #[allow(unused_mut)]
+        #[allow(unused_parens)]
+        #[allow(unused_variables)]
+        impl<$first, $($F,)*> Future for $StructName<$first, $($F,)*>
+        where
+            $first: TryFuture,
+            $($F: TryFuture<Error = $first::Error>,)*
+        {
+            type Output = Result<($first::Ok, $($F::Ok,)*), $first::Error>;
+
+            fn poll(
+                self: Pin<&mut Self>, cx: &mut Context<'_>
+            ) -> Poll<Self::Output> {
+                let mut this = self.project();
+                let futures = this.futures.project();
+
+                if *this.len > 0 {
+                    let mut readiness = this.wakers.readiness();
+                    readiness.set_waker(cx.waker());
+                    let any_ready = readiness.any_ready();
+                    drop(readiness);
+                    if any_ready {
+                        try_poll_ready_leaves!(this, futures, $first, $($F,)*);
+                    }
+                }
+
+                if *this.len <= 0 {
+                    let out = unsafe {(this.outputs as *const _ as *const ($first::Ok, $($F::Ok,)*)).read()};
+                    Poll::Ready(Ok(out))
+                } else {
+                    Poll::Pending
+                }
+            }
+        }
+
+        #[allow(unused_parens)]
+        impl<$first, $($F,)*> TryJoinTrait for ($first, $($F,)*)
+        where
+            $first: IntoFuture,
+            $first::IntoFuture: TryFuture,
+            $($F: IntoFuture,)*
+            $($F::IntoFuture: TryFuture<Error = <$first::IntoFuture as TryFuture>::Error>,)*
+        {
+            type Output = Result<(
+                <$first::IntoFuture as TryFuture>::Ok,
+                $(<$F::IntoFuture as TryFuture>::Ok,)*
+            ), <$first::IntoFuture as TryFuture>::Error>;
+            type Future = $StructName<$first::IntoFuture, $($F::IntoFuture,)*>;
+
+            fn try_join(self) -> Self::Future {
+                let ($first, $($F,)*): ($first, $($F,)*) = self;
+                const LEN: u32 = utils::tuple_len!($first, $($F,)*);
+                $StructName {
+                    len: LEN,
+                    futures: $mod_name::Futures($first.into_future(), $($F.into_future(),)* ()),
+                    outputs: (
+                        MaybeUninit::<<$first::IntoFuture as TryFuture>::Ok>::uninit(),
+                        $(MaybeUninit::<<$F::IntoFuture as TryFuture>::Ok>::uninit(),)*
+                    ),
+                    states: construct_tuple!(PollState::Pending, $first, $($F,)*),
+                    wakers: WakerArray::new(),
+                }
+            }
+        }
+    };
+}
+
+/// Waits for a zero-length tuple; trivially always succeeds.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+#[derive(Debug)]
+pub struct TryJoin0;
+
+impl Future for TryJoin0 {
+    type Output = Result<(), Infallible>;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl TryJoinTrait for () {
+    type Output = Result<(), Infallible>;
+    type Future = TryJoin0;
+
+    fn try_join(self) -> Self::Future {
+        TryJoin0
+    }
+}
+
+impl_try_join_tuple! { try_join_1 TryJoin1 A }
+impl_try_join_tuple! { try_join_2 TryJoin2 A B }
+impl_try_join_tuple! { try_join_3 TryJoin3 A B C }
+impl_try_join_tuple! { try_join_4 TryJoin4 A B C D }
+impl_try_join_tuple! { try_join_5 TryJoin5 A B C D E }
+impl_try_join_tuple! { try_join_6 TryJoin6 A B C D E F }
+impl_try_join_tuple! { try_join_7 TryJoin7 A B C D E F G }
+impl_try_join_tuple! { try_join_8 TryJoin8 A B C D E F G H }
+impl_try_join_tuple! { try_join_9 TryJoin9 A B C D E F G H I }
+impl_try_join_tuple! { try_join_10 TryJoin10 A B C D E F G H I J }
+impl_try_join_tuple! { try_join_11 TryJoin11 A B C D E F G H I J K }
+impl_try_join_tuple! { try_join_12 TryJoin12 A B C D E F G H I J K L }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::future;
+
+    #[test]
+    fn try_join_0() {
+        futures_lite::future::block_on(async {
+            assert_eq!(().try_join().await, Ok(()));
+        });
+    }
+
+    #[test]
+    fn try_join_all_ok() {
+        futures_lite::future::block_on(async {
+            let a = future::ready(Result::<_, std::io::Error>::Ok("hello"));
+            let b = future::ready(Result::<_, std::io::Error>::Ok(12));
+            assert_eq!((a, b).try_join().await.unwrap(), ("hello", 12));
+        });
+    }
+
+    #[test]
+    fn try_join_short_circuits() {
+        futures_lite::future::block_on(async {
+            let a = future::ready(Result::<&str, _>::Err("oh no"));
+            let b = future::ready(Result::<_, &str>::Ok(12));
+            assert_eq!((a, b).try_join().await, Err("oh no"));
+        });
+    }
+
+    #[test]
+    fn try_join_drops_completed_outputs_on_short_circuit() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Debug)]
+        struct DropCounter<'a>(&'a AtomicUsize);
+
+        impl Drop for DropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let drops = AtomicUsize::new(0);
+        futures_lite::future::block_on(async {
+            let a = future::ready(Result::<_, &str>::Ok(DropCounter(&drops)));
+            let b = future::ready(Result::<_, &str>::Ok(DropCounter(&drops)));
+            let c = future::ready(Result::<DropCounter<'_>, _>::Err("oh no"));
+            assert_eq!((a, b, c).try_join().await.unwrap_err(), "oh no");
+        });
+        // `a` and `b` resolved to `Ok` before `c` errored; both of their
+        // outputs must have been dropped exactly once via the short-circuit
+        // path, with no leak and no double-drop.
+        assert_eq!(drops.load(Ordering::SeqCst), 2);
+    }
+}
@@ -13,7 +13,7 @@ pub(crate) use pin::{get_pin_mut, get_pin_mut_from_vec, iter_pin_mut, iter_pin_m
 pub(crate) use poll_state::MaybeDone;
 pub(crate) use poll_state::{PollArray, PollState, PollVec};
 pub(crate) use rng::RandomGenerator;
-pub(crate) use tuple::{gen_conditions, permutations};
+pub(crate) use tuple::{construct_tuple, gen_conditions, permutations};
 pub(crate) use wakers::{WakerArray, WakerVec};
 pub(crate) use indexer::Indexer;
 
@@ -0,0 +1,11 @@
+/// Build a tuple of `$value` repeated once per identifier in `$F`, used to
+/// give every leaf of a tuple combinator the same initial state or type.
+macro_rules! construct_tuple {
+    (@inner $value:tt, $ignore:ident) => {
+        $value
+    };
+    ($value:path, $($F:ident,)*) => {
+        ($(construct_tuple!(@inner $value, $F),)*)
+    };
+}
+pub(crate) use construct_tuple;